@@ -0,0 +1,75 @@
+// checks.rs
+// Pinocchio 演示程序 - 账户校验模块
+// 这个文件集中存放所有攻击面相关的账户校验逻辑（签名者、所有权、PDA 派生），
+// 使各指令处理函数能够以可组合的方式复用一致且语义正确的检查。
+
+// 导入 pinocchio 框架组件
+use pinocchio::{
+    account_info::AccountInfo,              // 账户信息结构体
+    program_error::ProgramError,            // 程序错误类型
+    pubkey::{create_program_address, Pubkey}, // 公钥相关功能：创建程序派生地址
+};
+
+// 复用 instructions 模块中的 PDA 派生与缓存 bump 读取逻辑
+use crate::instructions::{derive_vault, read_cached_bump};
+// 类型化错误
+use crate::error::VaultError;
+
+/// 要求账户必须是交易签名者
+///
+/// # 返回值
+/// - `Ok(())`: 账户已签名
+/// - `Err(MissingRequiredSignature)`: 账户缺少必需的签名
+pub fn require_signer(account: &AccountInfo) -> Result<(), ProgramError> {
+    if !account.is_signer() {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+    Ok(())
+}
+
+/// 要求账户必须归指定程序所有
+///
+/// # 参数
+/// - `account`: 待校验的账户
+/// - `owner`: 期望的所有者程序 ID
+///
+/// # 返回值
+/// - `Ok(())`: 所有权匹配
+/// - `Err(InvalidAccountOwner)`: 所有权不匹配
+pub fn require_owned_by(account: &AccountInfo, owner: &Pubkey) -> Result<(), ProgramError> {
+    if !account.is_owned_by(owner) {
+        return Err(ProgramError::InvalidAccountOwner);
+    }
+    Ok(())
+}
+
+/// 要求 `vault` 必须是 `owner` 派生出的 vault PDA，返回其 bump seed
+///
+/// 优先读取 vault 数据中缓存的 bump，并用 `create_program_address`（单次哈希）
+/// 重建 PDA 与 `vault.key()` 比较；若缓存缺失或校验失败，则回退到完整的
+/// `find_program_address` 搜索，从而在保证正确性的同时尽量避免昂贵的循环。
+///
+/// # 返回值
+/// - `Ok(bump)`: 校验通过的 bump seed
+/// - `Err(VaultError::VaultPdaMismatch)`: 提供的 vault 并非该所有者派生的 PDA
+pub fn require_vault_pda(
+    owner: &AccountInfo,
+    vault: &AccountInfo,
+) -> Result<u8, ProgramError> {
+    // 快速路径：使用缓存的 bump，单次哈希重建 PDA
+    if let Some(bump) = read_cached_bump(vault) {
+        let seeds = [b"vault".as_ref(), owner.key().as_ref(), &[bump]];
+        if let Ok(pda) = create_program_address(&seeds, &crate::ID) {
+            if &pda == vault.key() {
+                return Ok(bump);
+            }
+        }
+    }
+
+    // 慢速路径：缓存缺失或校验失败，回退到完整搜索
+    let (expected_pda, bump) = derive_vault(owner);
+    if vault.key() != &expected_pda {
+        return Err(VaultError::VaultPdaMismatch.into());
+    }
+    Ok(bump)
+}
@@ -0,0 +1,60 @@
+// error.rs
+// Pinocchio 演示程序 - 错误类型模块
+// 这个文件定义程序特有的类型化错误 `VaultError`，并将其映射到
+// `ProgramError::Custom(n)`，使用稳定的数字编码，让前端（以及 IDL 消费者）
+// 能把自定义编码还原为可读的错误信息。
+
+// 导入 pinocchio 框架组件
+use pinocchio::program_error::ProgramError; // 程序错误类型
+use pinocchio_log::log; // 日志记录功能
+
+/// vault 程序特有的类型化错误
+///
+/// 每个变体对应一个稳定的自定义编码（`ProgramError::Custom(n)` 中的 `n`），
+/// 供客户端将数字编码映射回可读信息。为保证向后兼容，请勿调整已有变体的
+/// 数值，新错误只能追加在末尾。
+///
+/// # 自定义编码一览
+/// - `0` `ZeroAmount`           金额为零，被拒绝
+/// - `1` `VaultPdaMismatch`     提供的 vault 并非所有者派生的 PDA
+/// - `2` `NothingToWithdraw`    扣除租金最低限额后没有可提取的余额
+/// - `3` `UnauthorizedOwner`    签名者并非该 vault 记录的所有者
+/// - `4` `VaultNotInitialized`  vault 账户数据未初始化或长度不足
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[repr(u32)]
+pub enum VaultError {
+    /// 金额为零，被拒绝
+    ZeroAmount = 0,
+    /// 提供的 vault 并非所有者派生的 PDA
+    VaultPdaMismatch = 1,
+    /// 扣除租金最低限额后没有可提取的余额
+    NothingToWithdraw = 2,
+    /// 签名者并非该 vault 记录的所有者
+    UnauthorizedOwner = 3,
+    /// vault 账户数据未初始化或长度不足
+    VaultNotInitialized = 4,
+}
+
+impl VaultError {
+    /// 返回错误变体的名称，用于日志记录
+    pub fn name(self) -> &'static str {
+        match self {
+            VaultError::ZeroAmount => "ZeroAmount",
+            VaultError::VaultPdaMismatch => "VaultPdaMismatch",
+            VaultError::NothingToWithdraw => "NothingToWithdraw",
+            VaultError::UnauthorizedOwner => "UnauthorizedOwner",
+            VaultError::VaultNotInitialized => "VaultNotInitialized",
+        }
+    }
+}
+
+/// 将 `VaultError` 转换为 `ProgramError::Custom`
+///
+/// 转换时在失败现场记录错误名称日志，方便链上调试；数字编码保持稳定，
+/// 以便客户端把 `Custom(n)` 还原为对应的错误变体。
+impl From<VaultError> for ProgramError {
+    fn from(error: VaultError) -> Self {
+        log!("VaultError::{}", error.name());
+        ProgramError::Custom(error as u32)
+    }
+}
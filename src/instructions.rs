@@ -19,6 +19,9 @@ use pinocchio_log::log; // 日志记录功能
 use pinocchio_system::instructions::{CreateAccount, Transfer as SystemTransfer}; // 系统指令：创建账户和转账
 use shank::ShankInstruction; // Shank 指令宏，用于生成 IDL
 
+use crate::checks::{require_owned_by, require_signer, require_vault_pda}; // 账户校验助手
+use crate::error::VaultError; // 类型化错误
+
 /// Shank IDL facade 枚举，描述所有程序指令及其所需的账户
 /// 这个枚举仅用于 IDL（接口定义语言）生成，不会影响运行时行为
 /// 它为外部客户端（如前端应用）提供程序的接口定义
@@ -44,6 +47,40 @@ pub enum ProgramIx {
     #[account(1, writable, name = "vault", desc = "Vault PDA for lamports")]
     #[account(2, name = "program", desc = "Program Address")]
     Withdraw {},
+
+    /// 在两个 vault PDA 之间直接转移 lamports，无需经过所有者钱包
+    /// 这个指令把调用者 vault 中的 lamports 直接移动到接收者的 vault 中，
+    /// 由程序对两个 PDA 的 lamports 进行直接调整（两者均归程序所有）
+    #[account(
+        0,
+        signer,
+        writable,
+        name = "owner",
+        desc = "Source vault owner and authority"
+    )]
+    #[account(1, writable, name = "source_vault", desc = "Caller vault PDA (source)")]
+    #[account(2, name = "recipient", desc = "Recipient wallet that owns the destination vault")]
+    #[account(
+        3,
+        writable,
+        name = "recipient_vault",
+        desc = "Recipient vault PDA (destination)"
+    )]
+    #[account(4, name = "program", desc = "Program Address")]
+    #[account(5, name = "system_program", desc = "System Program Address")]
+    Pay { amount: u64 },
+
+    /// 关闭 vault 并把全部 lamports（含租金最低限额）退回所有者
+    /// 账户数据会被清零，从而让运行时回收该账户，用户得以完全取回其 SOL
+    #[account(
+        0,
+        signer,
+        writable,
+        name = "owner",
+        desc = "Vault owner and authority"
+    )]
+    #[account(1, writable, name = "vault", desc = "Vault PDA to close")]
+    Close {},
 }
 
 /// 从指令数据中解析 u64 金额
@@ -68,7 +105,7 @@ fn parse_amount(data: &[u8]) -> Result<u64, ProgramError> {
 
     // 验证金额不为零
     if amt == 0 {
-        return Err(ProgramError::InvalidInstructionData);
+        return Err(VaultError::ZeroAmount.into());
     }
 
     Ok(amt)
@@ -85,10 +122,165 @@ fn parse_amount(data: &[u8]) -> Result<u64, ProgramError> {
 /// # 说明
 /// - 使用 "vault" 和所有者公钥作为种子来派生 PDA
 /// - 确保每个所有者有唯一的 vault 地址
-fn derive_vault(owner: &AccountInfo) -> (Pubkey, u8) {
+pub(crate) fn derive_vault(owner: &AccountInfo) -> (Pubkey, u8) {
     find_program_address(&[b"vault", owner.key().as_ref()], &crate::ID)
 }
 
+/// 当前 vault 头部的版本标签
+/// 客户端可据此识别 vault 账户数据的布局版本
+const VAULT_VERSION: u8 = 1;
+
+/// vault 账户数据头部布局（固定偏移，便于客户端解析）
+///
+/// ```text
+/// 偏移    长度    字段
+/// [0]     1       version   版本标签（当前为 VAULT_VERSION）
+/// [1]     1       bump      缓存的 PDA bump seed
+/// [2..34] 32      owner     所有者公钥（用于校验）
+/// ```
+///
+/// 缓存 bump 可以让后续指令使用 `create_program_address`（单次哈希）重建
+/// PDA，而无需重复执行 `find_program_address` 的循环搜索。
+const VAULT_VERSION_OFFSET: usize = 0;
+const VAULT_BUMP_OFFSET: usize = 1;
+const VAULT_OWNER_OFFSET: usize = 2;
+/// vault 头部总长度（version + bump + owner）
+const VAULT_HEADER_SIZE: usize = 1 + 1 + core::mem::size_of::<Pubkey>();
+
+/// 将 vault 头部（版本、缓存 bump、所有者公钥）写入账户数据
+///
+/// # 参数
+/// - `vault`: vault 账户信息（必须可写且已分配足够空间）
+/// - `bump`: `derive_vault` 返回的 bump seed
+/// - `owner_key`: 所有者公钥
+fn write_vault_header(vault: &AccountInfo, bump: u8, owner_key: &Pubkey) -> ProgramResult {
+    let mut data = vault.try_borrow_mut_data()?;
+    if data.len() < VAULT_HEADER_SIZE {
+        return Err(ProgramError::AccountDataTooSmall);
+    }
+    data[VAULT_VERSION_OFFSET] = VAULT_VERSION;
+    data[VAULT_BUMP_OFFSET] = bump;
+    data[VAULT_OWNER_OFFSET..VAULT_OWNER_OFFSET + core::mem::size_of::<Pubkey>()]
+        .copy_from_slice(owner_key.as_ref());
+    Ok(())
+}
+
+/// 从 vault 账户数据中读取缓存的 bump seed
+///
+/// # 返回值
+/// - `Some(bump)`: 头部版本匹配时返回缓存的 bump
+/// - `None`: 数据过短或版本标签不匹配（需回退到完整搜索）
+pub(crate) fn read_cached_bump(vault: &AccountInfo) -> Option<u8> {
+    let data = vault.try_borrow_data().ok()?;
+    if data.len() < VAULT_HEADER_SIZE || data[VAULT_VERSION_OFFSET] != VAULT_VERSION {
+        return None;
+    }
+    Some(data[VAULT_BUMP_OFFSET])
+}
+
+/// total_deposited 字段偏移（紧跟在 owner 之后）
+const VAULT_TOTAL_DEPOSITED_OFFSET: usize = VAULT_OWNER_OFFSET + core::mem::size_of::<Pubkey>();
+/// deposit_count 字段偏移
+const VAULT_DEPOSIT_COUNT_OFFSET: usize = VAULT_TOTAL_DEPOSITED_OFFSET + size_of::<u64>();
+/// reserved 前向兼容尾部的偏移
+const VAULT_RESERVED_OFFSET: usize = VAULT_DEPOSIT_COUNT_OFFSET + size_of::<u32>();
+/// reserved 尾部长度，为未来的 v2 布局升级预留空间
+const VAULT_RESERVED_LEN: usize = 16;
+
+/// vault 的链上状态结构
+///
+/// 这个结构持久化 vault 的记账信息，使客户端可以查询存款历史，
+/// 而不再让 vault 仅仅是一个 lamport 桶。采用固定偏移、小端字节序的
+/// 手写序列化（no_std 环境，不依赖任何派生宏），字节布局如下：
+///
+/// ```text
+/// 偏移      长度    字段
+/// [0]       1       version          版本标签
+/// [1]       1       bump             缓存的 PDA bump seed
+/// [2..34]   32      owner            所有者公钥
+/// [34..42]  8       total_deposited  累计存入的 lamports（小端）
+/// [42..46]  4       deposit_count    存款次数（小端）
+/// [46..62]  16      reserved         预留尾部，保证 v2 布局向后兼容
+/// ```
+///
+/// `reserved` 置于尾部，这样未来扩展字段时旧客户端仍可按既有偏移解析。
+pub struct VaultState {
+    pub version: u8,                        // 布局版本标签
+    pub bump: u8,                           // 缓存的 PDA bump seed
+    pub owner: Pubkey,                      // 所有者公钥
+    pub total_deposited: u64,               // 累计存入的 lamports
+    pub deposit_count: u32,                 // 存款次数
+    pub reserved: [u8; VAULT_RESERVED_LEN], // 前向兼容预留
+}
+
+impl VaultState {
+    /// 序列化后的字节长度
+    pub const LEN: usize = VAULT_RESERVED_OFFSET + VAULT_RESERVED_LEN;
+
+    /// 从 vault 账户数据反序列化出 `VaultState`
+    ///
+    /// # 错误情况
+    /// - 账户数据长度不足 [`VaultState::LEN`]
+    pub fn load(vault: &AccountInfo) -> Result<Self, ProgramError> {
+        let data = vault.try_borrow_data()?;
+        if data.len() < Self::LEN {
+            return Err(VaultError::VaultNotInitialized.into());
+        }
+
+        let mut owner = Pubkey::default();
+        owner.copy_from_slice(
+            &data[VAULT_OWNER_OFFSET..VAULT_OWNER_OFFSET + core::mem::size_of::<Pubkey>()],
+        );
+
+        let total_deposited = u64::from_le_bytes(
+            data[VAULT_TOTAL_DEPOSITED_OFFSET..VAULT_TOTAL_DEPOSITED_OFFSET + size_of::<u64>()]
+                .try_into()
+                .unwrap(),
+        );
+        let deposit_count = u32::from_le_bytes(
+            data[VAULT_DEPOSIT_COUNT_OFFSET..VAULT_DEPOSIT_COUNT_OFFSET + size_of::<u32>()]
+                .try_into()
+                .unwrap(),
+        );
+
+        let mut reserved = [0u8; VAULT_RESERVED_LEN];
+        reserved.copy_from_slice(&data[VAULT_RESERVED_OFFSET..VAULT_RESERVED_OFFSET + VAULT_RESERVED_LEN]);
+
+        Ok(Self {
+            version: data[VAULT_VERSION_OFFSET],
+            bump: data[VAULT_BUMP_OFFSET],
+            owner,
+            total_deposited,
+            deposit_count,
+            reserved,
+        })
+    }
+
+    /// 将 `VaultState` 序列化写回 vault 账户数据
+    ///
+    /// # 错误情况
+    /// - 账户数据长度不足 [`VaultState::LEN`]
+    pub fn store(&self, vault: &AccountInfo) -> ProgramResult {
+        let mut data = vault.try_borrow_mut_data()?;
+        if data.len() < Self::LEN {
+            return Err(ProgramError::AccountDataTooSmall);
+        }
+
+        data[VAULT_VERSION_OFFSET] = self.version;
+        data[VAULT_BUMP_OFFSET] = self.bump;
+        data[VAULT_OWNER_OFFSET..VAULT_OWNER_OFFSET + core::mem::size_of::<Pubkey>()]
+            .copy_from_slice(self.owner.as_ref());
+        data[VAULT_TOTAL_DEPOSITED_OFFSET..VAULT_TOTAL_DEPOSITED_OFFSET + size_of::<u64>()]
+            .copy_from_slice(&self.total_deposited.to_le_bytes());
+        data[VAULT_DEPOSIT_COUNT_OFFSET..VAULT_DEPOSIT_COUNT_OFFSET + size_of::<u32>()]
+            .copy_from_slice(&self.deposit_count.to_le_bytes());
+        data[VAULT_RESERVED_OFFSET..VAULT_RESERVED_OFFSET + VAULT_RESERVED_LEN]
+            .copy_from_slice(&self.reserved);
+
+        Ok(())
+    }
+}
+
 /// 确保 vault 存在；如果不存在，则使用 PDA 种子创建它
 ///
 /// # 参数
@@ -103,16 +295,11 @@ fn derive_vault(owner: &AccountInfo) -> (Pubkey, u8) {
 /// - 如果 vault 不存在（lamports为0），则创建新的 vault 账户
 /// - 如果 vault 已存在，验证其所有权是否正确
 fn ensure_vault_exists(owner: &AccountInfo, vault: &AccountInfo) -> ProgramResult {
-    // 验证所有者是否为签名者
-    if !owner.is_signer() {
-        return Err(ProgramError::InvalidAccountOwner);
-    }
+    // 验证所有者是否为签名者（缺少签名应返回 MissingRequiredSignature）
+    require_signer(owner)?;
 
     // 检查 vault 是否为空（不存在）
     if vault.lamports() == 0 {
-        // 账户鉴别器大小，用于存储账户类型信息
-        const ACCOUNT_DISCRIMINATOR_SIZE: usize = 8;
-
         // 派生 vault PDA 和 bump seed
         let (_pda, bump) = derive_vault(owner);
 
@@ -124,8 +311,8 @@ fn ensure_vault_exists(owner: &AccountInfo, vault: &AccountInfo) -> ProgramResul
         ];
         let signer = Signer::from(&signer_seeds);
 
-        // 计算 vault 账户所需的大小
-        const VAULT_SIZE: usize = ACCOUNT_DISCRIMINATOR_SIZE + size_of::<u64>();
+        // 计算 vault 账户所需的大小（完整的 VaultState 布局）
+        const VAULT_SIZE: usize = VaultState::LEN;
 
         // 获取免除租金所需的最低 lamports 余额
         let needed_lamports = Rent::get()?.minimum_balance(VAULT_SIZE);
@@ -140,12 +327,13 @@ fn ensure_vault_exists(owner: &AccountInfo, vault: &AccountInfo) -> ProgramResul
         }
         .invoke_signed(&[signer])?; // 使用 PDA 签名执行创建操作
 
+        // 写入头部：缓存 bump，避免后续指令重复调用 find_program_address
+        write_vault_header(vault, bump, owner.key())?;
+
         log!("Vault created"); // 记录创建日志
     } else {
         // 如果 vault 已经存在，验证其所有权是否正确
-        if !vault.is_owned_by(&crate::ID) {
-            return Err(ProgramError::InvalidAccountOwner);
-        }
+        require_owned_by(vault, &crate::ID)?;
 
         log!("Vault already exists"); // 记录存在日志
     }
@@ -153,6 +341,74 @@ fn ensure_vault_exists(owner: &AccountInfo, vault: &AccountInfo) -> ProgramResul
     Ok(())
 }
 
+/// 确保接收者的 vault 存在；如果不存在，则由付款人创建它
+///
+/// # 参数
+/// - `payer`: 付款账户（必须是签名者，承担新账户的租金）
+/// - `recipient`: 接收者钱包账户（用于派生接收 vault 的 PDA）
+/// - `recipient_vault`: 接收者 vault 的账户信息
+///
+/// # 返回值
+/// - `ProgramResult`: 操作结果
+///
+/// # 功能
+/// - 如果接收 vault 不存在（lamports为0），则使用接收者公钥派生的 PDA 种子创建它
+/// - 如果接收 vault 已存在，验证其所有权是否正确
+///
+/// # 说明
+/// 与 [`ensure_vault_exists`] 不同，这里创建的 vault PDA 由 `recipient.key()` 派生，
+/// 但租金由 `payer`（来源所有者）支付，因为接收者并非本指令的签名者。
+fn ensure_recipient_vault_exists(
+    payer: &AccountInfo,
+    recipient: &AccountInfo,
+    recipient_vault: &AccountInfo,
+) -> ProgramResult {
+    // 检查接收 vault 是否为空（不存在）
+    if recipient_vault.lamports() == 0 {
+        // 使用接收者公钥派生 vault PDA 和 bump seed
+        let (_pda, bump) = derive_vault(recipient);
+
+        // 创建签名者种子数组（基于接收者公钥）
+        let signer_seeds = [
+            Seed::from(b"vault".as_slice()),
+            Seed::from(recipient.key().as_ref()),
+            Seed::from(core::slice::from_ref(&bump)),
+        ];
+        let signer = Signer::from(&signer_seeds);
+
+        // 计算 vault 账户所需的大小（完整的 VaultState 布局）
+        const VAULT_SIZE: usize = VaultState::LEN;
+
+        // 获取免除租金所需的最低 lamports 余额
+        let needed_lamports = Rent::get()?.minimum_balance(VAULT_SIZE);
+
+        // 创建接收 vault 账户（由付款人出资）
+        CreateAccount {
+            from: payer,               // 付款账户（来源所有者）
+            to: recipient_vault,       // 目标账户（接收 vault）
+            lamports: needed_lamports, // 初始 lamports 金额
+            space: VAULT_SIZE as u64,  // 账户空间大小
+            owner: &crate::ID,         // 账户所有者（当前程序）
+        }
+        .invoke_signed(&[signer])?; // 使用 PDA 签名执行创建操作
+
+        // 写入头部：缓存接收者 vault 的 bump
+        write_vault_header(recipient_vault, bump, recipient.key())?;
+
+        log!("Recipient vault created"); // 记录创建日志
+    } else {
+        // 如果接收 vault 已经存在，验证其所有权是否正确
+        require_owned_by(recipient_vault, &crate::ID)?;
+
+        // 同时验证接收 vault 确实是接收者公钥派生的正确 PDA
+        require_vault_pda(recipient, recipient_vault)?;
+
+        log!("Recipient vault already exists"); // 记录存在日志
+    }
+
+    Ok(())
+}
+
 /// 存款指令结构体
 /// 表示一个存款操作，包含相关的账户信息和存款金额
 pub struct Deposit<'a> {
@@ -185,6 +441,9 @@ impl<'a> Deposit<'a> {
         // 确保 vault 账户存在（如果不存在则创建）
         ensure_vault_exists(owner, vault)?;
 
+        // 校验 vault 确实是该所有者派生的 PDA，杜绝资金被路由到伪造的 vault
+        require_vault_pda(owner, vault)?;
+
         // 执行系统转账操作
         SystemTransfer {
             from: owner,      // 来源账户（所有者）
@@ -193,6 +452,13 @@ impl<'a> Deposit<'a> {
         }
         .invoke()?;
 
+        // 载入或初始化 vault 状态，累加记账信息后写回
+        // （新创建的 vault 数据区已清零，因此可直接当作初始状态加载）
+        let mut state = VaultState::load(vault)?;
+        state.total_deposited = state.total_deposited.saturating_add(amount);
+        state.deposit_count = state.deposit_count.saturating_add(1);
+        state.store(vault)?;
+
         // 记录存款成功日志
         log!("{} Lamports deposited to vault", amount);
         Ok(())
@@ -238,8 +504,9 @@ impl<'a> TryFrom<(&'a [u8], &'a [AccountInfo])> for Deposit<'a> {
 /// 取款指令结构体
 /// 表示一个取款操作，包含相关的账户信息
 pub struct Withdraw<'a> {
-    pub owner: &'a AccountInfo, // 取款所有者账户
-    pub vault: &'a AccountInfo, // 来源 vault 账户
+    pub owner: &'a AccountInfo,  // 取款所有者账户
+    pub vault: &'a AccountInfo,  // 来源 vault 账户
+    pub amount: Option<u64>,     // 指定取款金额；为 None 时提取租金最低限额之上的全部余额
 }
 
 impl<'a> Withdraw<'a> {
@@ -259,25 +526,34 @@ impl<'a> Withdraw<'a> {
     /// - 执行 lamports 转移
     /// - 记录取款操作日志
     ///
+    /// # 取款金额
+    /// - `amount` 为 `Some(n)`：精确提取 `n` lamports，若提取后无法保持租金免除则返回 `InsufficientFunds`
+    /// - 无论是否指定金额，vault 余额不超过租金最低限额时均返回 `VaultError::NothingToWithdraw`
+    /// - `amount` 为 `None`：提取租金最低限额之上的全部余额（保持原有行为）
+    ///
     /// # 返回值
     /// - `ProgramResult`: 操作结果
     pub fn process(self) -> ProgramResult {
-        let Withdraw { owner, vault } = self;
+        let Withdraw {
+            owner,
+            vault,
+            amount,
+        } = self;
 
         // 验证所有者是否为签名者
-        if !owner.is_signer() {
-            return Err(ProgramError::InvalidAccountOwner);
-        }
+        require_signer(owner)?;
 
         // 验证 vault 是否归程序所有
-        if !vault.is_owned_by(&crate::ID) {
-            return Err(ProgramError::InvalidAccountOwner);
-        }
+        require_owned_by(vault, &crate::ID)?;
 
         // 验证提供的 vault 账户是否是此所有者的正确 PDA
-        let (expected_vault_pda, _bump) = derive_vault(owner);
-        if vault.key() != &expected_vault_pda {
-            return Err(ProgramError::InvalidAccountData);
+        // 优先使用缓存的 bump（单次哈希），避免 find_program_address 的循环
+        let _bump = require_vault_pda(owner, vault)?;
+
+        // 额外的授权校验：链上状态中记录的 owner 必须与签名者一致
+        let state = VaultState::load(vault)?;
+        if &state.owner != owner.key() {
+            return Err(VaultError::UnauthorizedOwner.into());
         }
 
         // 计算在保持账户免除租金的同时可以提取的金额
@@ -288,11 +564,24 @@ impl<'a> Withdraw<'a> {
         // 检查是否有足够的余额可以提取
         if current <= min_balance {
             // 没有可提取的金额；保持行为严格以避免违反租金规定
-            return Err(ProgramError::InsufficientFunds);
+            return Err(VaultError::NothingToWithdraw.into());
         }
 
-        // 计算实际可提取金额（当前余额减去租金最低限额）
-        let withdraw_amount = current - min_balance;
+        // 租金最低限额之上的可提取上限
+        let available = current - min_balance;
+
+        // 根据是否指定金额决定实际提取额
+        let withdraw_amount = match amount {
+            // 精确提取：必须不超过可用额度（否则会破坏租金免除）
+            Some(requested) => {
+                if requested > available {
+                    return Err(ProgramError::InsufficientFunds);
+                }
+                requested
+            }
+            // 未指定：提取租金最低限额之上的全部余额
+            None => available,
+        };
 
         // 从 vault 向所有者转移 lamports
         // 使用作用域来管理 lamports 的可变借用
@@ -320,18 +609,24 @@ impl<'a> Withdraw<'a> {
 }
 
 /// 为 Withdraw 结构体实现 TryFrom trait
-/// 允许从账户信息创建 Withdraw 实例
-impl<'a> TryFrom<&'a [AccountInfo]> for Withdraw<'a> {
+/// 允许从原始指令数据和账户信息创建 Withdraw 实例
+impl<'a> TryFrom<(&'a [u8], &'a [AccountInfo])> for Withdraw<'a> {
     type Error = ProgramError;
 
-    /// 从账户信息创建 Withdraw 实例
+    /// 从指令数据和账户信息创建 Withdraw 实例
     ///
     /// # 参数
-    /// - `accounts`: 账户信息切片
+    /// - `value`: 包含指令数据字节切片和账户信息切片的元组
     ///
     /// # 返回值
     /// - `Result<Self, Self::Error>`: 成功时返回 Withdraw 实例，失败时返回错误
-    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+    ///
+    /// # 说明
+    /// - 指令数据为空时 `amount` 为 `None`（提取全部可用余额）
+    /// - 否则按 `parse_amount` 解析出精确取款金额
+    fn try_from(value: (&'a [u8], &'a [AccountInfo])) -> Result<Self, Self::Error> {
+        let (data, accounts) = value;
+
         // 验证账户数量是否足够
         if accounts.len() < 2 {
             return Err(ProgramError::NotEnoughAccountKeys);
@@ -341,7 +636,237 @@ impl<'a> TryFrom<&'a [AccountInfo]> for Withdraw<'a> {
         let owner = &accounts[0]; // 第一个账户是所有者
         let vault = &accounts[1]; // 第二个账户是 vault
 
+        // 解析可选的取款金额：数据为空表示提取全部可用余额
+        let amount = if data.is_empty() {
+            None
+        } else {
+            Some(parse_amount(data)?)
+        };
+
         // 创建并返回 Withdraw 实例
+        Ok(Self {
+            owner,
+            vault,
+            amount,
+        })
+    }
+}
+
+/// 支付指令结构体
+/// 表示一次 vault 到 vault 的直接转账，包含来源/接收者相关账户和转账金额
+pub struct Pay<'a> {
+    pub owner: &'a AccountInfo,           // 来源 vault 的所有者（签名者）
+    pub source_vault: &'a AccountInfo,    // 来源 vault PDA
+    pub recipient: &'a AccountInfo,       // 接收者钱包（派生接收 vault）
+    pub recipient_vault: &'a AccountInfo, // 接收者 vault PDA
+    pub amount: u64,                      // 转账金额（lamports）
+}
+
+impl<'a> Pay<'a> {
+    /// 支付指令的标识符（discriminator）
+    /// 在指令数据中第一个字节使用 2 表示支付指令
+    pub const DISCRIMINATOR: &'a u8 = &2;
+
+    /// 处理支付指令
+    ///
+    /// 把 lamports 从调用者的 vault PDA 直接转移到接收者的 vault PDA，
+    /// 而不经过所有者钱包。由于两个 vault 都归程序所有，程序可以直接
+    /// 调整它们的 lamports（无法对自己拥有的 lamports 使用 `invoke_signed` 转账）
+    ///
+    /// # 功能
+    /// - 验证所有者签名
+    /// - 验证 source_vault 是 `derive_vault(owner)` 派生的 PDA 且归程序所有
+    /// - 根据 `recipient.key()` 派生接收 vault，若不存在则创建
+    /// - 在保持来源 vault 免除租金的前提下转移 `amount` lamports
+    /// - 记录支付操作日志
+    ///
+    /// # 返回值
+    /// - `ProgramResult`: 操作结果
+    pub fn process(self) -> ProgramResult {
+        let Pay {
+            owner,
+            source_vault,
+            recipient,
+            recipient_vault,
+            amount,
+        } = self;
+
+        // 验证所有者是否为签名者
+        require_signer(owner)?;
+
+        // 验证 source_vault 是否归程序所有
+        require_owned_by(source_vault, &crate::ID)?;
+
+        // 验证提供的 source_vault 是否是此所有者的正确 PDA
+        // 优先使用缓存的 bump（单次哈希），避免 find_program_address 的循环
+        let _bump = require_vault_pda(owner, source_vault)?;
+
+        // 确保接收者的 vault 存在（如果不存在则由所有者付费创建）
+        ensure_recipient_vault_exists(owner, recipient, recipient_vault)?;
+
+        // 计算在保持来源 vault 免除租金的同时可以转移的金额
+        let data_len = source_vault.data_len();
+        let min_balance = Rent::get()?.minimum_balance(data_len);
+        let current = source_vault.lamports();
+
+        // 转账后来源 vault 必须保留其租金最低限额
+        if current < min_balance || current - min_balance < amount {
+            return Err(ProgramError::InsufficientFunds);
+        }
+
+        // 从来源 vault 中扣除金额
+        {
+            let mut source_lamports = source_vault.try_borrow_mut_lamports()?;
+            *source_lamports = source_lamports
+                .checked_sub(amount)
+                .ok_or(ProgramError::InsufficientFunds)?;
+        }
+
+        // 向接收者 vault 添加金额
+        {
+            let mut recipient_lamports = recipient_vault.try_borrow_mut_lamports()?;
+            *recipient_lamports = recipient_lamports
+                .checked_add(amount)
+                .ok_or(ProgramError::InsufficientFunds)?;
+        }
+
+        // 记录支付成功日志
+        log!("{} lamports paid to recipient vault", amount);
+        Ok(())
+    }
+}
+
+/// 为 Pay 结构体实现 TryFrom trait
+/// 允许从原始指令数据和账户信息创建 Pay 实例
+impl<'a> TryFrom<(&'a [u8], &'a [AccountInfo])> for Pay<'a> {
+    type Error = ProgramError;
+
+    /// 从指令数据和账户信息创建 Pay 实例
+    ///
+    /// # 参数
+    /// - `value`: 包含指令数据字节切片和账户信息切片的元组
+    ///
+    /// # 返回值
+    /// - `Result<Self, Self::Error>`: 成功时返回 Pay 实例，失败时返回错误
+    fn try_from(value: (&'a [u8], &'a [AccountInfo])) -> Result<Self, Self::Error> {
+        let (data, accounts) = value;
+
+        // 验证账户数量是否足够
+        if accounts.len() < 4 {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        }
+
+        // 提取账户信息
+        let owner = &accounts[0]; // 第一个账户是来源所有者
+        let source_vault = &accounts[1]; // 第二个账户是来源 vault
+        let recipient = &accounts[2]; // 第三个账户是接收者钱包
+        let recipient_vault = &accounts[3]; // 第四个账户是接收者 vault
+
+        // 解析转账金额
+        let amount = parse_amount(data)?;
+
+        // 创建并返回 Pay 实例
+        Ok(Self {
+            owner,
+            source_vault,
+            recipient,
+            recipient_vault,
+            amount,
+        })
+    }
+}
+
+/// 关闭指令结构体
+/// 表示一次 vault 关闭操作，把全部 lamports（含租金最低限额）退回所有者
+pub struct Close<'a> {
+    pub owner: &'a AccountInfo, // vault 所有者账户
+    pub vault: &'a AccountInfo, // 待关闭的 vault 账户
+}
+
+impl<'a> Close<'a> {
+    /// 关闭指令的标识符（discriminator）
+    /// 在指令数据中第一个字节使用 3 表示关闭指令
+    pub const DISCRIMINATOR: &'a u8 = &3;
+
+    /// 处理关闭指令
+    ///
+    /// 将 vault 的全部 lamports（包括租金最低限额）退回所有者，并清零账户数据，
+    /// 使运行时在本次交易结束后回收该账户，从而让用户完全取回其 SOL
+    ///
+    /// # 功能
+    /// - 验证所有者签名
+    /// - 验证 vault 所有权与 PDA 正确性
+    /// - 将全部 lamports 转移给所有者
+    /// - 清零账户数据以触发运行时回收
+    ///
+    /// # 返回值
+    /// - `ProgramResult`: 操作结果
+    pub fn process(self) -> ProgramResult {
+        let Close { owner, vault } = self;
+
+        // 验证所有者是否为签名者
+        require_signer(owner)?;
+
+        // 验证 vault 是否归程序所有
+        require_owned_by(vault, &crate::ID)?;
+
+        // 验证提供的 vault 账户是否是此所有者的正确 PDA
+        let _bump = require_vault_pda(owner, vault)?;
+
+        // 计算 vault 的全部余额（含租金最低限额）
+        let amount = vault.lamports();
+
+        // 第一步：将 vault 的全部 lamports 清零
+        {
+            let mut vault_lamports = vault.try_borrow_mut_lamports()?;
+            *vault_lamports = 0;
+        }
+
+        // 第二步：把全部 lamports 退回所有者
+        {
+            let mut owner_lamports = owner.try_borrow_mut_lamports()?;
+            *owner_lamports = owner_lamports
+                .checked_add(amount)
+                .ok_or(ProgramError::InsufficientFunds)?;
+        }
+
+        // 第三步：清零账户数据，使运行时回收该账户
+        {
+            let mut data = vault.try_borrow_mut_data()?;
+            for byte in data.iter_mut() {
+                *byte = 0;
+            }
+        }
+
+        // 记录关闭成功日志
+        log!("{} lamports reclaimed, vault closed", amount);
+        Ok(())
+    }
+}
+
+/// 为 Close 结构体实现 TryFrom trait
+/// 允许从账户信息创建 Close 实例
+impl<'a> TryFrom<&'a [AccountInfo]> for Close<'a> {
+    type Error = ProgramError;
+
+    /// 从账户信息创建 Close 实例
+    ///
+    /// # 参数
+    /// - `accounts`: 账户信息切片
+    ///
+    /// # 返回值
+    /// - `Result<Self, Self::Error>`: 成功时返回 Close 实例，失败时返回错误
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        // 验证账户数量是否足够
+        if accounts.len() < 2 {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        }
+
+        // 提取账户信息
+        let owner = &accounts[0]; // 第一个账户是所有者
+        let vault = &accounts[1]; // 第二个账户是 vault
+
+        // 创建并返回 Close 实例
         Ok(Self { owner, vault })
     }
 }
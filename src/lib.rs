@@ -28,6 +28,16 @@ pub mod instructions;
 // 重新导出指令模块中的所有公共项，方便外部使用
 pub use instructions::*;
 
+// 导入账户校验模块
+// checks 模块集中存放可组合的账户校验助手（签名者、所有权、PDA 派生）
+pub mod checks;
+
+// 导入错误类型模块
+// error 模块定义类型化的 VaultError 及其到 ProgramError::Custom 的映射
+pub mod error;
+// 重新导出错误类型，方便外部使用
+pub use error::VaultError;
+
 // 声明程序的唯一标识符
 // 这个 ID 用于在 Solana 区块链上唯一标识这个程序
 // 在部署程序之前需要生成一个新的 ID
@@ -52,6 +62,8 @@ declare_id!("GMYuTSUDK5psTjN45KTCWrMNfSdDbRHdnY1zzpgVDYgG");
 /// - 指令数据的第一个字节作为指令标识符（discriminator）
 /// - 0: 存款指令 (Deposit)
 /// - 1: 取款指令 (Withdraw)
+/// - 2: 支付指令 (Pay)
+/// - 3: 关闭指令 (Close)
 /// - 其他: 无效指令数据错误
 fn process_instruction(
     _program_id: &Pubkey,
@@ -66,9 +78,20 @@ fn process_instruction(
             Deposit::try_from((data, accounts))?.process()
         }
         // 处理取款指令：指令标识符为 1
-        Some((Withdraw::DISCRIMINATOR, _)) => {
-            // 从账户信息创建 Withdraw 结构体并执行处理
-            Withdraw::try_from(accounts)?.process()
+        Some((Withdraw::DISCRIMINATOR, data)) => {
+            // 从数据和账户信息创建 Withdraw 结构体并执行处理
+            // 指令数据可选：为空表示提取全部可用余额，否则为精确取款金额
+            Withdraw::try_from((data, accounts))?.process()
+        }
+        // 处理支付指令：指令标识符为 2
+        Some((Pay::DISCRIMINATOR, data)) => {
+            // 从数据和账户信息创建 Pay 结构体并执行处理
+            Pay::try_from((data, accounts))?.process()
+        }
+        // 处理关闭指令：指令标识符为 3
+        Some((Close::DISCRIMINATOR, _)) => {
+            // 从账户信息创建 Close 结构体并执行处理
+            Close::try_from(accounts)?.process()
         }
         // 处理无效指令：指令标识符不在支持范围内
         _ => Err(ProgramError::InvalidInstructionData),